@@ -1,7 +1,9 @@
+use crate::util::{Hash, Member, Role};
 use crate::App;
-use crate::util::Role;
 
-use chrono::Timelike;
+use std::time::{Duration, Instant};
+
+use chrono::format::{Item, StrftimeItems};
 use tui::backend::Backend;
 use tui::layout::{Constraint, Corner, Direction, Layout, Rect};
 use tui::style::{Color, Style};
@@ -10,6 +12,9 @@ use tui::widgets::{Block, Borders, List, ListItem, Paragraph};
 use tui::Frame;
 use unicode_width::UnicodeWidthStr;
 
+// How long a typing notification stays on screen after it's received
+const TYPING_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) {
     let has_members = app
         .data
@@ -20,15 +25,33 @@ pub fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) {
         .map(|channel| !channel.members.is_empty())
         .unwrap_or(false);
 
+    // The member panel is too narrow to show a full Jami ID, so the highlighted
+    // member's full URI is rendered as a full-width status line instead.
+    let selected_member_uri = has_members
+        .then(|| selected_member_uri(app))
+        .flatten();
+
+    let area = if let Some(uri) = &selected_member_uri {
+        let vchunks = Layout::default()
+            .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
+            .direction(Direction::Vertical)
+            .split(f.size());
+        let status = Paragraph::new(Span::styled(uri.clone(), Style::default().fg(Color::Gray)));
+        f.render_widget(status, vchunks[0]);
+        vchunks[1]
+    } else {
+        f.size()
+    };
+
     let chunks = match has_members {
         false => Layout::default()
                     .constraints([Constraint::Ratio(1, 4), Constraint::Ratio(3, 4)].as_ref())
                     .direction(Direction::Horizontal)
-                    .split(f.size()),
+                    .split(area),
         true => Layout::default()
                     .constraints([Constraint::Ratio(1, 4), Constraint::Ratio(5, 8), Constraint::Ratio(1, 8)].as_ref())
                     .direction(Direction::Horizontal)
-                    .split(f.size())
+                    .split(area)
     };
 
     let channel_list_width = chunks[0].width.saturating_sub(2) as usize;
@@ -93,37 +116,78 @@ fn draw_chat<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
         0
     };
 
+    let typing_label = app
+        .data
+        .channels
+        .state
+        .selected()
+        .and_then(|idx| app.data.channels.items.get(idx))
+        .and_then(|channel| channel.users_typing.as_ref())
+        .filter(|(since, _)| since.elapsed() < TYPING_TIMEOUT)
+        .and_then(|(_, hashes)| typing_label(app, hashes));
+
+    let mut constraints = vec![Constraint::Min(0)];
+    if typing_label.is_some() {
+        constraints.push(Constraint::Length(1));
+    }
+    constraints.push(Constraint::Length(
+        num_input_lines as u16 + 2 + extra_cursor_line,
+    ));
+
     let chunks = Layout::default()
-        .constraints(
-            [
-                Constraint::Min(0),
-                Constraint::Length(num_input_lines as u16 + 2 + extra_cursor_line),
-            ]
-            .as_ref(),
-        )
+        .constraints(constraints)
         .direction(Direction::Vertical)
         .split(area);
 
     draw_messages(f, app, chunks[0]);
 
+    let input_idx = if let Some(label) = &typing_label {
+        let typing = Paragraph::new(Span::styled(
+            label.clone(),
+            Style::default().fg(Color::DarkGray),
+        ));
+        f.render_widget(typing, chunks[1]);
+        2
+    } else {
+        1
+    };
+
     let input = Paragraph::new(Text::from(input))
         .block(Block::default().borders(Borders::ALL).title("Input"));
-    f.render_widget(input, chunks[1]);
+    f.render_widget(input, chunks[input_idx]);
     f.set_cursor(
         // Put cursor past the end of the input text
-        chunks[1].x + ((app.data.input_cursor as u16) % text_width as u16) + 1,
+        chunks[input_idx].x + ((app.data.input_cursor as u16) % text_width as u16) + 1,
         // Move one line down, from the border to the input line
-        chunks[1].y + (app.data.input_cursor as u16 / (text_width as u16)) + 1,
+        chunks[input_idx].y + (app.data.input_cursor as u16 / (text_width as u16)) + 1,
     );
 }
 
+// Format the "X is typing…" line, pluralized per the number of typing users. An
+// empty hash list means the notification was cleared but hasn't expired yet, so it
+// renders nothing rather than leaving a blank row above the input.
+fn typing_label(app: &App, hashes: &[Hash]) -> Option<String> {
+    match hashes {
+        [] => None,
+        [a] => Some(format!("{} is typing…", app.data.profile_manager.display_name(a))),
+        [a, b] => Some(format!(
+            "{} and {} are typing…",
+            app.data.profile_manager.display_name(a),
+            app.data.profile_manager.display_name(b),
+        )),
+        _ => Some(String::from("Several people are typing…")),
+    }
+}
+
 fn draw_messages<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-    let messages = app
+    let selected_channel = app
         .data
         .channels
         .state
         .selected()
-        .and_then(|idx| app.data.channels.items.get(idx))
+        .and_then(|idx| app.data.channels.items.get(idx));
+
+    let messages = selected_channel
         .map(|channel| &channel.messages[..])
         .unwrap_or(&[]);
 
@@ -133,12 +197,7 @@ fn draw_messages<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
         .max()
         .unwrap_or(0);
 
-    let description = app
-        .data
-        .channels
-        .state
-        .selected()
-        .and_then(|idx| app.data.channels.items.get(idx))
+    let description = selected_channel
         .map(|channel| &*channel.description)
         .unwrap_or("Messages");
     let room_description = match description {
@@ -147,54 +206,96 @@ fn draw_messages<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
     };
 
     let width = area.width - 2; // without borders
-    let max_lines = area.height;
+    let max_lines = area.height.saturating_sub(2) as usize; // without borders
 
     let time_style = Style::default().fg(Color::Yellow);
-    let messages = messages
-        .iter()
-        .rev()
-        // we can't show more messages atm and don't have messages navigation
-        .take(max_lines as usize)
-        .map(|msg| {
-            let arrived_at = msg.arrived_at.with_timezone(&chrono::Local);
+    let mention_style = Style::default().bg(Color::DarkGray);
+    let local_name = app.data.profile_manager.display_name(&app.data.account_hash);
 
-            let time = Span::styled(
-                format!("{:02}:{:02} ", arrived_at.hour(), arrived_at.minute()),
+    // Wrap a single message into its rendered lines. Kept as a closure rather than
+    // built up front for every message: on a long-lived channel that would wrap the
+    // entire history on every frame, when scrolling only ever needs messages inside
+    // the visible window (see `scroll_window` below).
+    let build_lines = |msg: &_| -> Vec<Spans> {
+        let time = if app.data.date_shown {
+            let arrived_at = msg.arrived_at.with_timezone(&chrono::Local);
+            Some(Span::styled(
+                format!("{} ", format_timestamp(arrived_at, &app.data.date_format)),
                 time_style,
-            );
-            let from = displayed_name(&msg.from, true);
-            let from = Span::styled(
-                textwrap::indent(&from, &" ".repeat(max_username_width - from.width())),
-                Style::default().fg(user_color(&msg.from)),
-            );
-            let delimeter = Span::from(": ");
-
-            let prefix_width = (time.width() + from.width() + delimeter.width()) as u16;
-            let indent = " ".repeat(prefix_width.into());
-            let message = msg.message.clone();
-            let lines =
-                textwrap::wrap_iter(message.as_str(), width.saturating_sub(prefix_width).into());
-
-            let spans: Vec<Spans> = lines
-                .enumerate()
-                .map(|(idx, line)| {
-                    let res = if idx == 0 {
-                        vec![
-                            time.clone(),
-                            from.clone(),
-                            delimeter.clone(),
-                            Span::from(line.to_string()),
-                        ]
-                    } else {
-                        vec![Span::from(format!("{}{}", indent, line))]
-                    };
-                    Spans::from(res)
-                })
-                .collect();
-            spans
+            ))
+        } else {
+            None
+        };
+        let from = displayed_name(&msg.from, true);
+        let from = Span::styled(
+            textwrap::indent(&from, &" ".repeat(max_username_width - from.width())),
+            Style::default().fg(user_color(&msg.from, app.data.truecolor)),
+        );
+        let delimeter = Span::from(": ");
+
+        let time_width = time.as_ref().map(|time| time.width()).unwrap_or(0) as u16;
+        let prefix_width = time_width + from.width() as u16 + delimeter.width() as u16;
+        let indent = " ".repeat(prefix_width.into());
+        let message = msg.message.clone();
+        let lines =
+            textwrap::wrap_iter(message.as_str(), width.saturating_sub(prefix_width).into());
+        let is_mention = contains_mention(&message, local_name);
+
+        lines
+            .enumerate()
+            .map(|(idx, line)| {
+                let line_span = if is_mention {
+                    Span::styled(line.to_string(), mention_style)
+                } else {
+                    Span::from(line.to_string())
+                };
+                let res = if idx == 0 {
+                    let mut spans = Vec::with_capacity(4);
+                    if let Some(time) = &time {
+                        spans.push(time.clone());
+                    }
+                    spans.push(from.clone());
+                    spans.push(delimeter.clone());
+                    spans.push(line_span);
+                    spans
+                } else {
+                    vec![Span::from(indent.clone()), line_span]
+                };
+                Spans::from(res)
+            })
+            .collect()
+    };
+
+    let raw_scroll_offset = selected_channel
+        .map(|channel| channel.scroll_offset)
+        .unwrap_or(0);
+
+    // `cache[i]` wraps the message `i` positions back from the most
+    // recent one, built lazily (and cached) so `scroll_window` only pays for the
+    // messages it actually decides to look at.
+    let mut cache: Vec<Option<Vec<Spans>>> = vec![None; messages.len()];
+    let line_count_at = |idx: usize, cache: &mut Vec<Option<Vec<Spans>>>| -> usize {
+        if cache[idx].is_none() {
+            let msg = &messages[messages.len() - 1 - idx];
+            cache[idx] = Some(build_lines(msg));
+        }
+        cache[idx].as_ref().unwrap().len()
+    };
+
+    let (ranges, _scroll_offset) =
+        scroll_window(messages.len(), raw_scroll_offset, max_lines, |idx| {
+            line_count_at(idx, &mut cache)
         });
 
-    let mut items: Vec<_> = messages.map(|s| ListItem::new(Text::from(s))).collect();
+    let mut items: Vec<ListItem> = ranges
+        .into_iter()
+        .map(|(idx, start, end)| {
+            // `scroll_window` only ever returns indices it already asked
+            // `line_count_at` for, so the cache entry is always populated here.
+            let lines = cache[idx].as_ref().unwrap();
+            ListItem::new(Text::from(lines[start..end].to_vec()))
+        })
+        .collect();
 
     if let Some(selected_idx) = app.data.channels.state.selected() {
         let unread_messages = app.data.channels.items[selected_idx].unread_messages;
@@ -219,25 +320,71 @@ fn draw_messages<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
     f.render_widget(list, area);
 }
 
-fn draw_members<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-    let members = app
-        .data
+// Admins first, then present members, then absent ones, so the most relevant
+// participants are always reachable near the top. Shared by `selected_member_uri`
+// and `draw_members` so the status line and the panel's highlight can never disagree
+// about ordering.
+fn sorted_members<'a>(app: &App, members: &'a [Member]) -> Vec<&'a Member> {
+    let mut members: Vec<_> = members.iter().collect();
+    members.sort_by_key(|member| {
+        let present = app
+            .data
+            .tracked_presences
+            .get(&member.hash)
+            .copied()
+            .unwrap_or(false);
+        match (member.role, present) {
+            (Role::Admin, _) => 0,
+            (_, true) => 1,
+            (_, false) => 2,
+        }
+    });
+    members
+}
+
+fn selected_channel_members<'a>(app: &'a App) -> &'a [Member] {
+    app.data
         .channels
         .state
         .selected()
         .and_then(|idx| app.data.channels.items.get(idx))
         .map(|channel| &channel.members[..])
-        .unwrap_or(&[]);
+        .unwrap_or(&[])
+}
 
-    let max_lines = area.height;
+// The member panel is only `Constraint::Ratio(1, 8)` wide, nowhere near enough room
+// to show a full Jami ID inline, so the highlighted member's full URI is surfaced
+// separately as a full-width status line (see `draw`). Resolved directly from
+// `selected_member_hash` against the current channel's members, not from
+// `members_state.selected()`, which is only written later in the frame by
+// `draw_members` and would still hold the previous channel's index right after a
+// channel switch.
+fn selected_member_uri(app: &App) -> Option<String> {
+    let hash = app.data.selected_member_hash.as_ref()?;
+    let members = sorted_members(app, selected_channel_members(app));
+    let member = members.into_iter().find(|member| member.hash == *hash)?;
+    let name = app.data.profile_manager.display_name(&member.hash);
+    Some(format!("{} — {}", name, member.hash))
+}
 
+fn draw_members<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
     let present_style = Style::default().fg(Color::White);
     let absent_style = Style::default().fg(Color::Red);
-    let members = members
-        .iter()
-        .rev()
-        // we can't show more members atm and don't have members navigation
-        .take(max_lines as usize)
+
+    let members = sorted_members(app, selected_channel_members(app));
+
+    // The list is re-sorted every frame since presence can flip at any time, so the
+    // highlight is keyed to the member's hash rather than a positional index -
+    // otherwise it would silently jump to a different member when the order changes.
+    let selected_idx = app
+        .data
+        .selected_member_hash
+        .as_ref()
+        .and_then(|hash| members.iter().position(|member| member.hash == *hash));
+    app.data.members_state.select(selected_idx);
+
+    let items: Vec<ListItem> = members
+        .into_iter()
         .map(|member| {
             let present = app.data.tracked_presences.get(&member.hash);
             let style = match present {
@@ -251,28 +398,56 @@ fn draw_members<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
             };
 
             let name = app.data.profile_manager.display_name(&member.hash);
-            let uri = Span::styled(
-                format!("{} {}", role, name),
-                style,
-            );
-
-            uri
-        });
+            let label = format!("{} {}", role, name);
 
-    let items: Vec<_> = members.map(|s| ListItem::new(Text::from(s))).collect();
+            ListItem::new(Text::from(Span::styled(label, style)))
+        })
+        .collect();
 
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL),
-        )
+        .block(Block::default().borders(Borders::ALL))
         .style(Style::default().fg(Color::White))
+        .highlight_style(Style::default().add_modifier(tui::style::Modifier::BOLD))
         .start_corner(Corner::TopLeft);
-    f.render_widget(list, area);
+    f.render_stateful_widget(list, area, &mut app.data.members_state);
 }
 
-// Randomly but deterministically choose a color for a username
-fn user_color(username: &str) -> Color {
+// Perceived-brightness threshold (0-255 scale) a username color must clear to stay
+// readable against the dark background.
+const MIN_LUMINANCE: f64 = 60.0;
+
+// Deterministically choose a color for a username. Truecolor terminals get a hashed
+// 24-bit RGB color so members rarely collide; others fall back to the 16-color palette.
+fn user_color(username: &str, truecolor: bool) -> Color {
+    if truecolor {
+        user_color_rgb(username)
+    } else {
+        user_color_16(username)
+    }
+}
+
+fn user_color_rgb(username: &str) -> Color {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash as _, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    username.hash(&mut hasher);
+    let mut seed = hasher.finish();
+
+    loop {
+        let r = (seed & 0xFF) as u8;
+        let g = ((seed >> 8) & 0xFF) as u8;
+        let b = ((seed >> 16) & 0xFF) as u8;
+        let luminance = 0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64;
+        if luminance >= MIN_LUMINANCE {
+            return Color::Rgb(r, g, b);
+        }
+        // Too dark to read — rotate the hash and try the next candidate.
+        seed = seed.wrapping_mul(0x2545_F491_4F6C_DD1D).wrapping_add(1);
+    }
+}
+
+fn user_color_16(username: &str) -> Color {
     use Color::*;
     const COLORS: &[Color] = &[Red, Green, Yellow, Blue, Magenta, Cyan, Gray];
     let idx = username
@@ -283,6 +458,97 @@ fn user_color(username: &str) -> Color {
     COLORS[idx]
 }
 
+// Whole-word match: a hit only counts when the characters surrounding the matched
+// name are non-alphanumeric (or the string boundary), so "alanna" doesn't match "alan".
+fn contains_mention(message: &str, name: &str) -> bool {
+    if name.is_empty() {
+        return false;
+    }
+
+    message.match_indices(name).any(|(start, matched)| {
+        let before_ok = message[..start]
+            .chars()
+            .next_back()
+            .map_or(true, |c| !c.is_alphanumeric());
+        let end = start + matched.len();
+        let after_ok = message[end..]
+            .chars()
+            .next()
+            .map_or(true, |c| !c.is_alphanumeric());
+        before_ok && after_ok
+    })
+}
+
+// Used whenever the configured `date_format` doesn't parse as a strftime pattern.
+const DEFAULT_DATE_FORMAT: &str = "%H:%M";
+
+// `DateTime::format` panics if the pattern contains an invalid specifier (a trailing
+// `%` or an unknown `%Q`), and `date_format` comes straight from user config, so the
+// pattern is validated before it's ever handed to `format!` on the draw path. An
+// empty pattern is also rejected here: `StrftimeItems` happily yields zero items for
+// it (no `Item::Error`), which would otherwise silently render as an empty string.
+fn format_timestamp(arrived_at: chrono::DateTime<chrono::Local>, pattern: &str) -> String {
+    if pattern.is_empty() {
+        return arrived_at.format(DEFAULT_DATE_FORMAT).to_string();
+    }
+    let items: Vec<Item> = StrftimeItems::new(pattern).collect();
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return arrived_at.format(DEFAULT_DATE_FORMAT).to_string();
+    }
+    arrived_at.format_with_items(items.into_iter()).to_string()
+}
+
+// Works out which rendered lines fall inside the visible `max_lines` window, newest
+// message first, after dropping `scroll_offset` lines off the bottom. `line_count_at`
+// is called with increasing indices (0 = newest message) only as far as needed, so a
+// caller can wrap messages lazily rather than wrapping an entire channel's history
+// just to scroll to the bottom of it. Returns `(message_index, start_line, end_line)`
+// ranges to render, plus the scroll offset actually used once clamped to the
+// available history (so you can't scroll past the top).
+fn scroll_window(
+    message_count: usize,
+    scroll_offset: usize,
+    max_lines: usize,
+    mut line_count_at: impl FnMut(usize) -> usize,
+) -> (Vec<(usize, usize, usize)>, usize) {
+    let mut walk = |skip_target: usize| -> (Vec<(usize, usize, usize)>, usize, bool) {
+        let mut skip = skip_target;
+        let mut budget = max_lines;
+        let mut total_lines = 0usize;
+        let mut exhausted = true;
+        let mut ranges = Vec::new();
+        for idx in 0..message_count {
+            if budget == 0 {
+                exhausted = false;
+                break;
+            }
+            let len = line_count_at(idx);
+            total_lines += len;
+            if skip >= len {
+                skip -= len;
+                continue;
+            }
+            let end = len - skip;
+            let take = end.min(budget);
+            let start = end - take;
+            ranges.push((idx, start, end));
+            budget -= take;
+            skip = 0;
+        }
+        (ranges, total_lines, exhausted)
+    };
+
+    let (ranges, total_lines, exhausted) = walk(scroll_offset);
+    if exhausted {
+        let max_scroll = total_lines.saturating_sub(max_lines);
+        if scroll_offset > max_scroll {
+            let (ranges, _, _) = walk(max_scroll);
+            return (ranges, max_scroll);
+        }
+    }
+    (ranges, scroll_offset)
+}
+
 fn displayed_name(name: &str, first_name_only: bool) -> &str {
     if first_name_only {
         let space_pos = name.find(' ').unwrap_or_else(|| name.len());
@@ -291,3 +557,139 @@ fn displayed_name(name: &str, first_name_only: bool) -> &str {
         &name
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scroll_window_fills_from_the_bottom_with_no_offset() {
+        // newest-first line counts: 1, 3, 2, 1 (total 7)
+        let counts = [1, 3, 2, 1];
+        let (ranges, offset) = scroll_window(counts.len(), 0, 3, |idx| counts[idx]);
+        assert_eq!(offset, 0);
+        // the 3 most recent lines: all of message 0 (1 line) and the bottom 2 of message 1
+        assert_eq!(ranges, vec![(0, 0, 1), (1, 1, 3)]);
+    }
+
+    #[test]
+    fn scroll_window_skips_lines_off_the_bottom() {
+        let counts = [1, 3, 2, 1];
+        // scroll up by 2: the bottom-most 2 lines are message 0's only line plus the
+        // bottom line of message 1, then the window fills with the next 3 lines up
+        let (ranges, offset) = scroll_window(counts.len(), 2, 3, |idx| counts[idx]);
+        assert_eq!(offset, 2);
+        assert_eq!(ranges, vec![(1, 0, 2), (2, 1, 2)]);
+    }
+
+    #[test]
+    fn scroll_window_slices_a_single_wrapped_message_mid_message() {
+        // a single message that wraps to many lines should be sliceable mid-message
+        let counts = [5];
+        let (ranges, offset) = scroll_window(counts.len(), 1, 2, |idx| counts[idx]);
+        assert_eq!(offset, 1);
+        // skip the bottom-most line (idx 4), then take the next 2 lines up (idx 2..4)
+        assert_eq!(ranges, vec![(0, 2, 4)]);
+    }
+
+    #[test]
+    fn scroll_window_clamps_an_offset_past_the_top_of_history() {
+        let counts = [2, 2];
+        // total is 4 lines; asking to scroll up by 10 can't be satisfied
+        let (ranges, offset) = scroll_window(counts.len(), 10, 3, |idx| counts[idx]);
+        assert_eq!(offset, 1); // max_scroll = total(4) - max_lines(3)
+        assert_eq!(ranges, vec![(0, 0, 1), (1, 0, 2)]);
+    }
+
+    #[test]
+    fn scroll_window_stops_without_visiting_older_history() {
+        let mut visited = Vec::new();
+        let counts = [2, 2, 2, 2, 2];
+        let (ranges, offset) = scroll_window(counts.len(), 0, 3, |idx| {
+            visited.push(idx);
+            counts[idx]
+        });
+        assert_eq!(offset, 0);
+        assert_eq!(ranges, vec![(0, 0, 2), (1, 1, 2)]);
+        // only the messages inside the visible window were ever asked for their
+        // line count - the rest of a long history is left untouched.
+        assert_eq!(visited, vec![0, 1]);
+    }
+
+    #[test]
+    fn format_timestamp_falls_back_on_invalid_pattern() {
+        let at = chrono::Local::now();
+        let expected = at.format(DEFAULT_DATE_FORMAT).to_string();
+        assert_eq!(format_timestamp(at, "%Q"), expected);
+        assert_eq!(format_timestamp(at, "%"), expected);
+    }
+
+    #[test]
+    fn format_timestamp_falls_back_on_empty_pattern() {
+        let at = chrono::Local::now();
+        assert_eq!(
+            format_timestamp(at, ""),
+            at.format(DEFAULT_DATE_FORMAT).to_string()
+        );
+    }
+
+    #[test]
+    fn format_timestamp_uses_valid_pattern() {
+        let at = chrono::Local::now();
+        assert_eq!(
+            format_timestamp(at, "%Y-%m-%d"),
+            at.format("%Y-%m-%d").to_string()
+        );
+    }
+
+    #[test]
+    fn contains_mention_matches_whole_word() {
+        assert!(contains_mention("hey alan, check this out", "alan"));
+        assert!(contains_mention("alan", "alan"));
+        assert!(contains_mention("cc @alan", "alan"));
+        assert!(contains_mention("alan:", "alan"));
+    }
+
+    #[test]
+    fn contains_mention_rejects_partial_word() {
+        assert!(!contains_mention("alanna is here", "alan"));
+        assert!(!contains_mention("not alana", "alan"));
+        assert!(!contains_mention("malan was here", "alan"));
+    }
+
+    #[test]
+    fn contains_mention_matches_at_string_boundaries() {
+        assert!(contains_mention("alan said hi", "alan"));
+        assert!(contains_mention("hi, this is alan", "alan"));
+    }
+
+    #[test]
+    fn contains_mention_empty_name_never_matches() {
+        assert!(!contains_mention("anything at all", ""));
+    }
+
+    #[test]
+    fn user_color_rgb_meets_luminance_floor() {
+        for name in ["alan", "alanna", "bob", "a", "", "the quick brown fox"] {
+            match user_color_rgb(name) {
+                Color::Rgb(r, g, b) => {
+                    let luminance = 0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64;
+                    assert!(
+                        luminance >= MIN_LUMINANCE,
+                        "color for {:?} was too dark: rgb({}, {}, {})",
+                        name,
+                        r,
+                        g,
+                        b
+                    );
+                }
+                other => panic!("expected an RGB color, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn user_color_rgb_is_deterministic() {
+        assert_eq!(user_color_rgb("alan"), user_color_rgb("alan"));
+    }
+}